@@ -8,7 +8,22 @@ where
 {
     pair: SeqPair<'a, T>,
     ops: &'a EditOperations<T>,
-    matrix: Vec<Vec<usize>>,
+    matrix: Vec<usize>,
+    dim: [usize; 2],
+    affix: Option<Affix<'a, T>>,
+}
+
+/// Bookkeeping for the prefix/suffix trimmed by `Matrix::align_trimmed`.
+struct Affix<'a, T>
+where
+    T: 'a,
+{
+    source: &'a [T],
+    target: &'a [T],
+    prefix_len: usize,
+    suffix_len: usize,
+    prefix_op: Option<&'a EditOperation<T>>,
+    suffix_op: Option<&'a EditOperation<T>>,
 }
 
 impl<'a, T> Matrix<'a, T> {
@@ -27,42 +42,157 @@ impl<'a, T> Matrix<'a, T> {
         let mut matrix = Matrix {
             pair,
             ops,
-            matrix: vec![vec![0; target_len]; source_len],
+            matrix: vec![0; source_len * target_len],
+            dim: [source_len, target_len],
+            affix: None,
         };
 
         // Fill first row. This is separated from the rest of the matrix fill
         // because we do not want to fill cell [0][0].
         for target_idx in 1..target_len {
-            matrix.matrix[0][target_idx] = ops.apply(&matrix, 0, target_idx)
+            let cost = ops.apply(&matrix, 0, target_idx)
                 .expect("No applicable operation");
+            matrix.set_cell(0, target_idx, cost);
         }
 
         // Fill the matrix
         for source_idx in 1..source_len {
             for target_idx in 0..target_len {
-                matrix.matrix[source_idx][target_idx] = ops.apply(&matrix, source_idx, target_idx)
+                let cost = ops.apply(&matrix, source_idx, target_idx)
                     .expect("No applicatble operation");
+                matrix.set_cell(source_idx, target_idx, cost);
             }
         }
 
         matrix
     }
 
+    /// Align two sequences, trimming a common prefix/suffix before building the DP matrix.
+    ///
+    /// `distance()` and `edit_script()` cover the full, untrimmed sequences.
+    pub fn align_trimmed(ops: &'a EditOperations<T>, source: &'a [T], target: &'a [T]) -> Matrix<'a, T>
+    where
+        T: Eq,
+    {
+        let max_affix = source.len().min(target.len());
+
+        let prefix_len = source
+            .iter()
+            .zip(target.iter())
+            .take(max_affix)
+            .take_while(|&(s, t)| s == t)
+            .count();
+
+        let suffix_len = source[prefix_len..]
+            .iter()
+            .rev()
+            .zip(target[prefix_len..].iter().rev())
+            .take(max_affix - prefix_len)
+            .take_while(|&(s, t)| s == t)
+            .count();
+
+        let mut matrix = Matrix::align(
+            ops,
+            &source[prefix_len..source.len() - suffix_len],
+            &target[prefix_len..target.len() - suffix_len],
+        );
+
+        // A trimmed element is equal in `source` and `target`, so backtracking a
+        // one-element alignment of it recovers whichever operation `ops` picks for
+        // equal elements (`match`, for the zero-cost Levenshtein case this crate
+        // targets). This assumes that op is what `ops` always prefers for equal
+        // elements; a cost model with its own zero-cost op for equal elements would
+        // need to expose that directly instead.
+        let prefix_op = if prefix_len > 0 {
+            Matrix::align(ops, &source[..1], &target[..1])
+                .edit_script()
+                .and_then(|script| script.into_iter().next())
+        } else {
+            None
+        };
+
+        let suffix_op = if suffix_len > 0 {
+            let source_last = source.len() - 1;
+            let target_last = target.len() - 1;
+            Matrix::align(ops, &source[source_last..], &target[target_last..])
+                .edit_script()
+                .and_then(|script| script.into_iter().next())
+        } else {
+            None
+        };
+
+        matrix.affix = Some(Affix {
+            source,
+            target,
+            prefix_len,
+            suffix_len,
+            prefix_op,
+            suffix_op,
+        });
+
+        matrix
+    }
+
+    /// Get the cost of a cell in the padded `(|source| + 1) x (|target| + 1)` matrix.
+    pub fn cell(&self, source_idx: usize, target_idx: usize) -> usize {
+        self.matrix[source_idx * self.dim[1] + target_idx]
+    }
+
+    fn set_cell(&mut self, source_idx: usize, target_idx: usize, cost: usize) {
+        let idx = source_idx * self.dim[1] + target_idx;
+        self.matrix[idx] = cost;
+    }
+
+    /// Get the operation that produced a cell, if any; `None` for `(0, 0)`.
+    ///
+    /// Computed on demand, not cached. This is a prerequisite accessor only:
+    /// no `EditOperations::apply`/`backtrack` in this tree consults it yet to
+    /// charge affine, "group-start" penalties.
+    pub fn group_op(&self, source_idx: usize, target_idx: usize) -> Option<&'a EditOperation<T>> {
+        if source_idx == 0 && target_idx == 0 {
+            return None;
+        }
+
+        self.ops.backtrack(self, source_idx, target_idx)
+    }
+
     /// Get the edit distance.
     pub fn distance(&self) -> usize {
-        self.matrix[self.matrix.len() - 1][self.matrix[0].len() - 1]
+        self.cell(self.dim[0] - 1, self.dim[1] - 1)
     }
 
-    pub fn edit_script(&self) -> Option<Vec<&'a EditOperation<T>>> {
+    /// Get the edit distance divided by the longer sequence's length; `0.0` when both are empty.
+    ///
+    /// The result is in `[0.0, 1.0]` when no insert, delete or substitute costs more than 1;
+    /// with larger costs it can exceed 1.0 (and `similarity_ratio()` can go below 0.0).
+    pub fn normalized_distance(&self) -> f64 {
+        let pair = self.seq_pair();
+        let longest = pair.source.len().max(pair.target.len());
+        if longest == 0 {
+            return 0.0;
+        }
+
+        self.distance() as f64 / longest as f64
+    }
+
+    /// Get `1.0 - normalized_distance()`.
+    pub fn similarity_ratio(&self) -> f64 {
+        1.0 - self.normalized_distance()
+    }
+
+    /// Walk the backtrack path to `(0, 0)`, forward order, as
+    /// `(op, source_idx, target_idx, new_source_idx, new_target_idx)` steps.
+    /// Shared by `edit_script()` and `aligned_pair()`.
+    fn backtrack_path(&self) -> Option<Vec<(&'a EditOperation<T>, usize, usize, usize, usize)>> {
         let mut source_idx = self.pair.source.len();
         let mut target_idx = self.pair.target.len();
-        let mut script = Vec::new();
+        let mut path = Vec::new();
 
         while let Some(op) = self.ops.backtrack(self, source_idx, target_idx) {
             let (new_source_idx, new_target_idx) = op.backtrack(source_idx, target_idx)?;
+            path.push((op, source_idx, target_idx, new_source_idx, new_target_idx));
             source_idx = new_source_idx;
             target_idx = new_target_idx;
-            script.push(op);
 
             if source_idx == 0 && target_idx == 0 {
                 break;
@@ -72,19 +202,117 @@ impl<'a, T> Matrix<'a, T> {
         assert_eq!(source_idx, 0, "Cannot backtrack to cell 0, 0");
         assert_eq!(target_idx, 0, "Cannot backtrack to cell 0, 0");
 
-        script.reverse();
+        path.reverse();
+        Some(path)
+    }
+
+    pub fn edit_script(&self) -> Option<Vec<&'a EditOperation<T>>> {
+        let mut script: Vec<&'a EditOperation<T>> = self.backtrack_path()?
+            .into_iter()
+            .map(|(op, ..)| op)
+            .collect();
+
+        if let Some(ref affix) = self.affix {
+            let mut full = Vec::with_capacity(affix.prefix_len + script.len() + affix.suffix_len);
+            if let Some(op) = affix.prefix_op {
+                full.extend(::std::iter::repeat(op).take(affix.prefix_len));
+            }
+            full.extend(script);
+            if let Some(op) = affix.suffix_op {
+                full.extend(::std::iter::repeat(op).take(affix.suffix_len));
+            }
+            script = full;
+        }
 
         Some(script)
     }
 
-    /// Get the cost matrix.
-    pub fn matrix(&self) -> &Vec<Vec<usize>> {
-        &self.matrix
+    /// Get the two sequences aligned against each other, with gaps as `None`.
+    pub fn aligned_pair(&self) -> Option<(Vec<Option<&'a T>>, Vec<Option<&'a T>>)> {
+        let path = self.backtrack_path()?;
+
+        let mut source_col = Vec::with_capacity(path.len());
+        let mut target_col = Vec::with_capacity(path.len());
+
+        for (_, source_idx, target_idx, new_source_idx, new_target_idx) in path {
+            source_col.push(if new_source_idx < source_idx {
+                Some(&self.pair.source[new_source_idx])
+            } else {
+                None
+            });
+            target_col.push(if new_target_idx < target_idx {
+                Some(&self.pair.target[new_target_idx])
+            } else {
+                None
+            });
+        }
+
+        if let Some(ref affix) = self.affix {
+            let mut full_source =
+                Vec::with_capacity(affix.prefix_len + source_col.len() + affix.suffix_len);
+            let mut full_target =
+                Vec::with_capacity(affix.prefix_len + target_col.len() + affix.suffix_len);
+
+            full_source.extend(affix.source[..affix.prefix_len].iter().map(Some));
+            full_target.extend(affix.target[..affix.prefix_len].iter().map(Some));
+
+            full_source.extend(source_col);
+            full_target.extend(target_col);
+
+            let source_suffix_start = affix.source.len() - affix.suffix_len;
+            let target_suffix_start = affix.target.len() - affix.suffix_len;
+            full_source.extend(affix.source[source_suffix_start..].iter().map(Some));
+            full_target.extend(affix.target[target_suffix_start..].iter().map(Some));
+
+            return Some((full_source, full_target));
+        }
+
+        Some((source_col, target_col))
+    }
+
+    /// Get `edit_script()` with consecutive identical operations coalesced into `(op, count)` pairs.
+    pub fn edit_script_coalesced(&self) -> Option<Vec<(&'a EditOperation<T>, usize)>> {
+        let script = self.edit_script()?;
+
+        let mut coalesced: Vec<(&'a EditOperation<T>, usize)> = Vec::new();
+        for op in script {
+            match coalesced.last_mut() {
+                Some(&mut (last_op, ref mut count)) if std::ptr::eq(last_op, op) => {
+                    *count += 1;
+                }
+                _ => coalesced.push((op, 1)),
+            }
+        }
+
+        Some(coalesced)
     }
 
-    /// Get the sequence pair associated with this cost matrix.
-    pub fn seq_pair(&self) -> &SeqPair<T> {
-        &self.pair
+    /// Get the cost matrix, reconstructed as nested vectors.
+    ///
+    /// Prefer `cell()` to avoid the extra allocation.
+    pub fn matrix(&self) -> Vec<Vec<usize>> {
+        self.matrix
+            .chunks(self.dim[1])
+            .map(|row| row.to_vec())
+            .collect()
+    }
+
+    /// Get the full sequence pair that was aligned.
+    ///
+    /// For a `Matrix` built with `align_trimmed()`, this is the original,
+    /// untrimmed pair passed in, not the trimmed middle the DP table was
+    /// actually built over.
+    pub fn seq_pair(&self) -> SeqPair<'a, T> {
+        match self.affix {
+            Some(ref affix) => SeqPair {
+                source: affix.source,
+                target: affix.target,
+            },
+            None => SeqPair {
+                source: self.pair.source,
+                target: self.pair.target,
+            },
+        }
     }
 }
 
@@ -181,6 +409,31 @@ mod tests {
             .collect()
     }
 
+    #[test]
+    fn edit_script_coalesced_test() {
+        let pineapple: Vec<char> = "pineapple".chars().collect();
+        let pen: Vec<char> = "pen".chars().collect();
+
+        let ops = levensthein(1, 1, 1);
+
+        let coalesced: Vec<(String, usize)> = Matrix::align(&ops, &pineapple, &pen)
+            .edit_script_coalesced()
+            .unwrap()
+            .into_iter()
+            .map(|(op, count)| (op.to_string(), count))
+            .collect();
+
+        assert_eq!(
+            coalesced,
+            vec![
+                ("match".to_string(), 1),
+                ("substitute".to_string(), 1),
+                ("match".to_string(), 1),
+                ("delete".to_string(), 6),
+            ]
+        );
+    }
+
     #[test]
     fn align_empty_test() {
         let empty: &[char] = &[];
@@ -199,4 +452,141 @@ mod tests {
             5
         );
     }
+
+    #[test]
+    fn aligned_pair_test() {
+        let pineapple: Vec<char> = "pineapple".chars().collect();
+        let pen: Vec<char> = "pen".chars().collect();
+
+        let ops = levensthein(1, 1, 1);
+
+        let (source, target) = Matrix::align(&ops, &pineapple, &pen).aligned_pair().unwrap();
+
+        assert_eq!(
+            source,
+            vec![
+                Some(&'p'),
+                Some(&'i'),
+                Some(&'n'),
+                Some(&'e'),
+                Some(&'a'),
+                Some(&'p'),
+                Some(&'p'),
+                Some(&'l'),
+                Some(&'e'),
+            ]
+        );
+        assert_eq!(
+            target,
+            vec![
+                Some(&'p'),
+                Some(&'e'),
+                Some(&'n'),
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+            ]
+        );
+    }
+
+    #[test]
+    fn group_op_test() {
+        // Sanity-checks the accessor itself (no affine cost model exists here
+        // to exercise group-start scoring against).
+        let pineapple: Vec<char> = "pineapple".chars().collect();
+        let pen: Vec<char> = "pen".chars().collect();
+
+        let ops = levensthein(1, 1, 1);
+        let matrix = Matrix::align(&ops, &pineapple, &pen);
+
+        let (source_len, target_len) = (pineapple.len(), pen.len());
+        assert_eq!(
+            matrix.group_op(source_len, target_len).map(ToString::to_string),
+            ops.backtrack(&matrix, source_len, target_len)
+                .map(ToString::to_string)
+        );
+        assert_eq!(matrix.group_op(0, 0), None);
+    }
+
+    #[test]
+    fn normalized_distance_test() {
+        let applet: Vec<char> = "applet".chars().collect();
+        let pineapple: Vec<char> = "pineapple".chars().collect();
+        let empty: &[char] = &[];
+
+        let ops = levensthein(1, 1, 1);
+
+        assert_eq!(
+            Matrix::align(&ops, &pineapple, &applet).normalized_distance(),
+            5.0 / 9.0
+        );
+        assert_eq!(
+            Matrix::align(&ops, &pineapple, &applet).similarity_ratio(),
+            1.0 - 5.0 / 9.0
+        );
+        assert_eq!(Matrix::align(&ops, empty, empty).normalized_distance(), 0.0);
+    }
+
+    #[test]
+    fn align_trimmed_test() {
+        let applet: Vec<char> = "applet".chars().collect();
+        let pineapple: Vec<char> = "pineapple".chars().collect();
+        let pen: Vec<char> = "pen".chars().collect();
+
+        let ops = levensthein(1, 1, 1);
+
+        assert_eq!(
+            Matrix::align_trimmed(&ops, &pineapple, &pen).distance(),
+            Matrix::align(&ops, &pineapple, &pen).distance()
+        );
+        assert_eq!(
+            Matrix::align_trimmed(&ops, &pineapple, &applet).distance(),
+            Matrix::align(&ops, &pineapple, &applet).distance()
+        );
+
+        assert_eq!(
+            Matrix::align_trimmed(&ops, &pineapple, &pen)
+                .edit_script()
+                .unwrap()
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>(),
+            Matrix::align(&ops, &pineapple, &pen)
+                .edit_script()
+                .unwrap()
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn align_trimmed_affix_accessors_test() {
+        // Shares the prefix "abcx" and the suffix "z", so both affixes are
+        // actually trimmed and the middle ("y" vs "") is a plain delete.
+        let source: Vec<char> = "abcxyz".chars().collect();
+        let target: Vec<char> = "abcxz".chars().collect();
+
+        let ops = levensthein(1, 1, 1);
+
+        let trimmed = Matrix::align_trimmed(&ops, &source, &target);
+        let untrimmed = Matrix::align(&ops, &source, &target);
+
+        let trimmed_pair = trimmed.seq_pair();
+        assert_eq!(trimmed_pair.source, source.as_slice());
+        assert_eq!(trimmed_pair.target, target.as_slice());
+
+        assert_eq!(
+            trimmed.normalized_distance(),
+            untrimmed.normalized_distance()
+        );
+
+        assert_eq!(
+            trimmed.aligned_pair().unwrap(),
+            untrimmed.aligned_pair().unwrap()
+        );
+    }
 }
\ No newline at end of file